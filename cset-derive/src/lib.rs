@@ -1,6 +1,8 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, Attribute, DataStruct, DeriveInput, Error, Meta, NestedMeta, Type};
+use syn::{
+    parse_macro_input, Attribute, DataEnum, DataStruct, DeriveInput, Error, Meta, NestedMeta, Type,
+};
 
 #[proc_macro_derive(Track, attributes(track))]
 pub fn macro_entry(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -8,10 +10,7 @@ pub fn macro_entry(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
     let expanded = match &input.data {
         syn::Data::Struct(data) => derive_tracked_struct(&input, data),
-        syn::Data::Enum(data) => {
-            syn::Error::new_spanned(data.enum_token, "Cannot derive Undo for enums")
-                .into_compile_error()
-        }
+        syn::Data::Enum(data) => derive_tracked_enum(&input, data),
         syn::Data::Union(data) => {
             syn::Error::new_spanned(data.union_token, "Cannot derive Undo for unions")
                 .into_compile_error()
@@ -26,16 +25,55 @@ struct TrackedField {
     ident: Ident,
     ty: Type,
     flattened_ident: Option<Ident>,
+    collection_elem_ty: Option<Type>,
+    /// `true` for `#[track(collection(scalar))]`: the element type is a
+    /// plain value with no `Track` impl of its own, so only whole-element
+    /// insert/remove/set are supported and no `edit_*` accessor is emitted.
+    collection_scalar: bool,
 }
 
 fn derive_tracked_struct(input: &DeriveInput, data: &DataStruct) -> TokenStream {
     let struct_ident = &input.ident;
+    let serde_support = has_container_attr(&input.attrs, "serde");
 
     for field in &data.fields {
         if field.ident.is_none() {
             return syn::Error::new_spanned(&data.fields, "Cannot derive Undo for tuple structs")
                 .to_compile_error();
         }
+
+        let is_flattened = field.attrs.iter().any(|attr| {
+            get_meta_items(attr).unwrap().iter().any(|meta| match meta {
+                NestedMeta::Meta(Meta::Path(path)) => path.is_ident("flatten"),
+                _ => false,
+            })
+        });
+        if is_flattened && simple_path_ident(&field.ty).is_none() {
+            return syn::Error::new_spanned(
+                &field.ty,
+                "#[track(flatten)] only supports a simple type path (e.g. `Bar`, not `foo::Bar` or `Foo<T>`)",
+            )
+            .to_compile_error();
+        }
+
+        let collection = collection_attr(&field.attrs);
+        if let Some(is_scalar) = collection {
+            let Some(elem_ty) = vec_elem_type(&field.ty) else {
+                return syn::Error::new_spanned(
+                    &field.ty,
+                    "#[track(collection)] only supports `Vec<T>` fields",
+                )
+                .to_compile_error();
+            };
+            if !is_scalar && simple_path_ident(&elem_ty).is_none() {
+                return syn::Error::new_spanned(
+                    &elem_ty,
+                    "#[track(collection)] element type must be a simple type path and derive \
+                     `Track`; use #[track(collection(scalar))] for plain-value elements",
+                )
+                .to_compile_error();
+            }
+        }
     }
 
     let fields = data
@@ -50,19 +88,27 @@ fn derive_tracked_struct(input: &DeriveInput, data: &DataStruct) -> TokenStream
                     _ => false,
                 })
             });
-            
+            let collection = collection_attr(&field.attrs);
+
             let ty = field.ty.clone();
             let flattened_ident = if is_flattened {
                 Some(flattened_struct_ident(&ty))
             } else {
                 None
             };
+            let collection_elem_ty = if collection.is_some() {
+                Some(vec_elem_type(&ty).unwrap())
+            } else {
+                None
+            };
 
             TrackedField {
                 index,
                 ident,
                 ty: field.ty.clone(),
                 flattened_ident,
+                collection_elem_ty,
+                collection_scalar: collection.unwrap_or(false),
             }
         })
         .collect::<Vec<_>>();
@@ -73,11 +119,14 @@ fn derive_tracked_struct(input: &DeriveInput, data: &DataStruct) -> TokenStream
         let TrackedField {
             ident,
             flattened_ident,
+            collection_elem_ty,
             ..
         } = field;
 
         if flattened_ident.is_some() {
             quote!(#ident: self.#ident.edit())
+        } else if collection_elem_ty.is_some() {
+            quote!(#ident: ::cset::CollectionDraft::new(&mut self.#ident))
         } else {
             quote!(#ident: ::cset::DraftField::new(&mut self.#ident))
         }
@@ -85,7 +134,7 @@ fn derive_tracked_struct(input: &DeriveInput, data: &DataStruct) -> TokenStream
 
     let apply_value_fields = fields
         .iter()
-        .filter(|field| field.flattened_ident.is_none())
+        .filter(|field| field.flattened_ident.is_none() && field.collection_elem_ty.is_none())
         .map(|field| {
             let TrackedField {
                 index, ident, ty, ..
@@ -122,6 +171,203 @@ fn derive_tracked_struct(input: &DeriveInput, data: &DataStruct) -> TokenStream
             }
         });
 
+    let apply_sequence_fields = fields
+        .iter()
+        .filter(|field| field.collection_elem_ty.is_some())
+        .map(|field| {
+            let TrackedField { index, ident, collection_scalar, .. } = field;
+            let apply_edit = if *collection_scalar {
+                quote!(|_element, _changeset| unreachable!(
+                    "#[track(collection(scalar))] fields have no element edits"
+                ))
+            } else {
+                quote!(|element, element_changeset| element.apply(element_changeset))
+            };
+
+            quote! {
+                #index => {
+                    let reverse_ops = ::cset::apply_sequence(
+                        &mut self.#ident,
+                        ops,
+                        #apply_edit,
+                    );
+                    reverse_changes.push(::cset::Change {
+                        field_id: change.field_id,
+                        value: ::cset::ChangeValue::Sequence(reverse_ops),
+                    });
+                }
+            }
+        });
+
+    let key_to_index_arms = fields.iter().map(|field| {
+        let TrackedField { index, ident, .. } = field;
+        let key = ident.to_string();
+        quote!(#key => ::std::option::Option::Some(#index))
+    });
+
+    let remap_value_fields = fields
+        .iter()
+        .filter(|field| field.flattened_ident.is_none() && field.collection_elem_ty.is_none())
+        .map(|field| {
+            let index = field.index;
+            quote! {
+                #index => ::cset::ChangeValue::Value(value),
+            }
+        });
+
+    let remap_changeset_fields = fields
+        .iter()
+        .filter_map(|field| field.flattened_ident.as_ref().map(|ident| (field, ident)))
+        .map(|(field, flattened_ident)| {
+            let index = field.index;
+            quote! {
+                #index => ::cset::ChangeValue::ChangeSet(#flattened_ident::remap_impl(inner, depth + 1)?),
+            }
+        });
+
+    let remap_sequence_fields = fields
+        .iter()
+        .filter_map(|field| field.collection_elem_ty.as_ref().map(|ty| (field, ty)))
+        .map(|(field, elem_ty)| {
+            let index = field.index;
+            let edit_arm = if field.collection_scalar {
+                quote!(::cset::SequenceOp::Edit(_) => unreachable!(
+                    "#[track(collection(scalar))] fields have no element edits"
+                ))
+            } else {
+                let elem_ident = flattened_struct_ident(elem_ty);
+                quote!(::cset::SequenceOp::Edit(cs) => ::cset::SequenceOp::Edit(#elem_ident::remap_impl(cs, 0)?))
+            };
+            quote! {
+                #index => {
+                    let mut remapped_ops = ::std::vec::Vec::with_capacity(ops.len());
+                    for edit in ops {
+                        let op = match edit.op {
+                            ::cset::SequenceOp::Insert(v) => ::cset::SequenceOp::Insert(v),
+                            ::cset::SequenceOp::Remove => ::cset::SequenceOp::Remove,
+                            ::cset::SequenceOp::Set(v) => ::cset::SequenceOp::Set(v),
+                            #edit_arm,
+                        };
+                        remapped_ops.push(::cset::SequenceEdit { index: edit.index, op });
+                    }
+                    ::cset::ChangeValue::Sequence(remapped_ops)
+                }
+            }
+        });
+
+    let serde_methods = if serde_support {
+        derive_struct_serde_methods(struct_ident, &fields[..])
+    } else {
+        quote!()
+    };
+
+    let lens_consts = fields
+        .iter()
+        .filter(|field| field.collection_elem_ty.is_none())
+        .map(|field| {
+            let TrackedField { index, ident, ty, .. } = field;
+            let lens_ident = format_ident!("{}_LENS", ident.to_string().to_uppercase());
+            quote! {
+                pub const #lens_ident: ::cset::FieldLens<Self, #ty> =
+                    ::cset::FieldLens::new(&[#index], |s| &s.#ident, |s| &mut s.#ident);
+            }
+        });
+
+    let focus_dyn_arms = fields
+        .iter()
+        .filter(|field| field.collection_elem_ty.is_none())
+        .map(|field| {
+            let TrackedField { index, ident, flattened_ident, .. } = field;
+            if flattened_ident.is_some() {
+                quote! {
+                    #index => {
+                        // A flatten field's own per-field sub-drafts hold
+                        // exclusive borrows into it, so there's nowhere a
+                        // `&Whole` reference to the flattened struct itself
+                        // could live alongside them; only a lens that
+                        // reaches *into* it (e.g. `Foo::BAR_LENS.then(Bar::Y_LENS)`)
+                        // can be focused.
+                        assert!(path.len() > 1, "cannot focus a lens that targets a flattened field itself, only a field within it");
+                        self.#ident.focus_dyn(&path[1..])
+                    }
+                }
+            } else {
+                quote! {
+                    #index => {
+                        let current = if let Some(value) = &self.#ident.draft {
+                            value
+                        } else {
+                            &*self.#ident.original
+                        };
+                        current as &dyn ::std::any::Any
+                    }
+                }
+            }
+        });
+
+    let set_with_dyn_arms = fields
+        .iter()
+        .filter(|field| field.collection_elem_ty.is_none())
+        .map(|field| {
+            let TrackedField { index, ident, ty, flattened_ident, .. } = field;
+            if flattened_ident.is_some() {
+                quote! {
+                    #index => {
+                        assert!(path.len() > 1, "cannot set_with a lens that targets a flattened field itself, only a field within it");
+                        self.#ident.set_with_dyn(&path[1..], value)
+                    }
+                }
+            } else {
+                quote! {
+                    #index => {
+                        self.#ident.draft = ::std::option::Option::Some(*value.downcast::<#ty>().unwrap());
+                    }
+                }
+            }
+        });
+
+    let lens_methods = quote! {
+        /// Reads the current effective value (draft if set, else original)
+        /// of the field `lens` points to, walking through any flattened
+        /// sub-drafts the path crosses.
+        ///
+        /// `lens` may target a plain field (e.g. `Foo::X_LENS`) or a field
+        /// *within* a flattened field (e.g. `Foo::BAR_LENS.then(Bar::Y_LENS)`),
+        /// but not a flattened field itself
+        /// (`Foo::BAR_LENS`) — the flattened sub-draft's own fields are each
+        /// borrowed separately, so there's no single effective value for
+        /// the whole flattened struct to read through a draft.
+        pub fn focus<Field: 'static>(&self, lens: &impl ::cset::Lens<#struct_ident, Field>) -> &Field {
+            self.focus_dyn(&lens.field_id().path())
+                .downcast_ref::<Field>()
+                .unwrap()
+        }
+
+        fn focus_dyn(&self, path: &[usize]) -> &dyn ::std::any::Any {
+            match path[0] {
+                #(#focus_dyn_arms)*
+                _ => panic!("lens targets a field this draft doesn't have"),
+            }
+        }
+
+        /// Sets the field `lens` points to, walking through any flattened
+        /// sub-drafts the path crosses, without the caller chaining the
+        /// generated `edit_*`/`set_*` accessors by hand.
+        ///
+        /// Same restriction as [Self::focus]: `lens` must not target a
+        /// flattened field itself, only a plain field or a field within it.
+        pub fn set_with<Field: 'static>(&mut self, lens: &impl ::cset::Lens<#struct_ident, Field>, value: Field) {
+            self.set_with_dyn(&lens.field_id().path(), ::std::boxed::Box::new(value));
+        }
+
+        fn set_with_dyn(&mut self, path: &[usize], value: ::std::boxed::Box<dyn ::std::any::Any>) {
+            match path[0] {
+                #(#set_with_dyn_arms)*
+                _ => panic!("lens targets a field this draft doesn't have"),
+            }
+        }
+    };
+
     quote! {
         impl #struct_ident {
             pub fn edit(&mut self) -> #draft_ident {
@@ -139,7 +385,8 @@ fn derive_tracked_struct(input: &DeriveInput, data: &DataStruct) -> TokenStream
                 let mut reverse_changes = Vec::new();
 
                 for change in changeset.changes {
-                    let field_index = change.field_id.field_index(depth);
+                    let field_index = Self::__cset_resolve_index(&change.field_id, depth)
+                        .unwrap_or_else(|| unreachable!("unknown field key in changeset; call ChangeSet::remap first"));
 
                     match change.value {
                         ::cset::ChangeValue::Value(value) => match field_index {
@@ -150,39 +397,857 @@ fn derive_tracked_struct(input: &DeriveInput, data: &DataStruct) -> TokenStream
                             #(#apply_changeset_fields,)*
                             _ => unreachable!(),
                         },
+                        ::cset::ChangeValue::Sequence(ops) => match field_index {
+                            #(#apply_sequence_fields,)*
+                            _ => unreachable!(),
+                        },
+                        ::cset::ChangeValue::Variant { .. } => {
+                            unreachable!("variant changes only apply to tracked enums")
+                        }
                     };
                 }
 
                 ::cset::ChangeSet::new::<#struct_ident>(reverse_changes)
             }
+
+            /// Resolves a [::cset::FieldId] segment's index by `key` first,
+            /// falling back to its stored `index` only when no key was
+            /// recorded (e.g. a [::cset::FieldLens]-addressed edit). Returns
+            /// `None` when a key no longer names any field, meaning the
+            /// changeset is stale and should have been run through
+            /// [::cset::ChangeSet::remap] first.
+            fn __cset_resolve_index(field_id: &::cset::FieldId, depth: usize) -> ::std::option::Option<usize> {
+                match field_id.field_key(depth) {
+                    ::std::option::Option::Some(key) => Self::__cset_field_key_to_index(key),
+                    ::std::option::Option::None => ::std::option::Option::Some(field_id.field_index(depth)),
+                }
+            }
+
+            fn __cset_field_key_to_index(key: &str) -> ::std::option::Option<usize> {
+                match key {
+                    #(#key_to_index_arms,)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn remap_impl(changeset: ::cset::ChangeSet, depth: usize) -> ::std::result::Result<::cset::ChangeSet, ::cset::UnknownField> {
+                assert!(changeset.for_type::<#struct_ident>());
+                let mut changes = Vec::new();
+                let mut unknown_keys = Vec::new();
+
+                for change in changeset.changes {
+                    let resolved_index = match change.field_id.field_key(depth) {
+                        ::std::option::Option::Some(key) => match Self::__cset_field_key_to_index(key) {
+                            ::std::option::Option::Some(index) => index,
+                            ::std::option::Option::None => {
+                                unknown_keys.push(key.to_string());
+                                continue;
+                            }
+                        },
+                        ::std::option::Option::None => change.field_id.field_index(depth),
+                    };
+
+                    let mut field_id = change.field_id;
+                    field_id.set_index(depth, resolved_index);
+
+                    let value = match change.value {
+                        ::cset::ChangeValue::Value(value) => match resolved_index {
+                            #(#remap_value_fields)*
+                            _ => unreachable!(),
+                        },
+                        ::cset::ChangeValue::ChangeSet(inner) => match resolved_index {
+                            #(#remap_changeset_fields)*
+                            _ => unreachable!(),
+                        },
+                        ::cset::ChangeValue::Sequence(ops) => match resolved_index {
+                            #(#remap_sequence_fields,)*
+                            _ => unreachable!(),
+                        },
+                        ::cset::ChangeValue::Variant { .. } => {
+                            unreachable!("variant changes only apply to tracked enums")
+                        }
+                    };
+
+                    changes.push(::cset::Change { field_id, value });
+                }
+
+                if !unknown_keys.is_empty() {
+                    return ::std::result::Result::Err(::cset::UnknownField { keys: unknown_keys });
+                }
+
+                ::std::result::Result::Ok(::cset::ChangeSet::new::<#struct_ident>(changes))
+            }
+
+            #serde_methods
+
+            #(#lens_consts)*
+        }
+
+        impl ::cset::Trackable for #struct_ident {
+            fn apply(&mut self, changeset: ::cset::ChangeSet) -> ::cset::ChangeSet {
+                self.apply_impl(changeset, 0)
+            }
+
+            fn remap(changeset: ::cset::ChangeSet) -> ::std::result::Result<::cset::ChangeSet, ::cset::UnknownField> {
+                Self::remap_impl(changeset, 0)
+            }
         }
 
         #draft_struct
+
+        impl<'b> #draft_ident<'b> {
+            #lens_methods
+        }
+    }
+}
+
+/// Generates `to_serializable`/`from_serializable` for a `#[track(serde)]`
+/// struct, plus the `_impl` pair threading `depth` the same way
+/// `apply`/`apply_impl` do.
+fn derive_struct_serde_methods(struct_ident: &Ident, fields: &[TrackedField]) -> TokenStream {
+    let to_value_fields = fields
+        .iter()
+        .filter(|field| field.flattened_ident.is_none() && field.collection_elem_ty.is_none())
+        .map(|field| {
+            let TrackedField { index, ty, .. } = field;
+            quote! {
+                #index => {
+                    let typed = v.downcast_ref::<#ty>().unwrap();
+                    ::cset::serde_support::SerializableValue::Value(::cset::serde_support::serde_json::to_value(typed).unwrap())
+                }
+            }
+        });
+
+    let to_changeset_fields = fields
+        .iter()
+        .filter_map(|field| field.flattened_ident.as_ref().map(|ident| (field, ident)))
+        .map(|(field, flattened_ident)| {
+            let index = field.index;
+            quote! {
+                #index => ::cset::serde_support::SerializableValue::ChangeSet(
+                    #flattened_ident::to_serializable_impl(cs, depth + 1),
+                ),
+            }
+        });
+
+    let to_sequence_fields = fields
+        .iter()
+        .filter_map(|field| field.collection_elem_ty.as_ref().map(|ty| (field, ty)))
+        .map(|(field, elem_ty)| {
+            let index = field.index;
+            let edit_arm = if field.collection_scalar {
+                quote!(::cset::SequenceOp::Edit(_) => unreachable!(
+                    "#[track(collection(scalar))] fields have no element edits"
+                ))
+            } else {
+                let elem_ident = flattened_struct_ident(elem_ty);
+                quote! {
+                    ::cset::SequenceOp::Edit(cs) => {
+                        ::cset::serde_support::SerializableSequenceOp::Edit(#elem_ident::to_serializable_impl(cs, 0))
+                    }
+                }
+            };
+            quote! {
+                #index => {
+                    let mut serial_ops = Vec::with_capacity(ops.len());
+                    for edit in ops {
+                        let op = match &edit.op {
+                            ::cset::SequenceOp::Insert(v) => {
+                                let typed = v.downcast_ref::<#elem_ty>().unwrap();
+                                ::cset::serde_support::SerializableSequenceOp::Insert(::cset::serde_support::serde_json::to_value(typed).unwrap())
+                            }
+                            ::cset::SequenceOp::Remove => ::cset::serde_support::SerializableSequenceOp::Remove,
+                            ::cset::SequenceOp::Set(v) => {
+                                let typed = v.downcast_ref::<#elem_ty>().unwrap();
+                                ::cset::serde_support::SerializableSequenceOp::Set(::cset::serde_support::serde_json::to_value(typed).unwrap())
+                            }
+                            #edit_arm,
+                        };
+                        serial_ops.push(::cset::serde_support::SerializableSequenceEdit { index: edit.index, op });
+                    }
+                    ::cset::serde_support::SerializableValue::Sequence(serial_ops)
+                }
+            }
+        });
+
+    let from_value_fields = fields
+        .iter()
+        .filter(|field| field.flattened_ident.is_none() && field.collection_elem_ty.is_none())
+        .map(|field| {
+            let TrackedField { index, ty, .. } = field;
+            quote! {
+                #index => {
+                    let typed: #ty = ::cset::serde_support::serde_json::from_value(v).unwrap();
+                    ::cset::ChangeValue::Value(::std::boxed::Box::new(typed))
+                }
+            }
+        });
+
+    let from_changeset_fields = fields
+        .iter()
+        .filter_map(|field| field.flattened_ident.as_ref().map(|ident| (field, ident)))
+        .map(|(field, flattened_ident)| {
+            let index = field.index;
+            quote! {
+                #index => ::cset::ChangeValue::ChangeSet(
+                    #flattened_ident::from_serializable_impl(cs, depth + 1)?,
+                ),
+            }
+        });
+
+    let from_sequence_fields = fields
+        .iter()
+        .filter_map(|field| field.collection_elem_ty.as_ref().map(|ty| (field, ty)))
+        .map(|(field, elem_ty)| {
+            let index = field.index;
+            let edit_arm = if field.collection_scalar {
+                quote!(::cset::serde_support::SerializableSequenceOp::Edit(_) => unreachable!(
+                    "#[track(collection(scalar))] fields have no element edits"
+                ))
+            } else {
+                let elem_ident = flattened_struct_ident(elem_ty);
+                quote! {
+                    ::cset::serde_support::SerializableSequenceOp::Edit(cs) => {
+                        ::cset::SequenceOp::Edit(#elem_ident::from_serializable_impl(cs, 0)?)
+                    }
+                }
+            };
+            quote! {
+                #index => {
+                    let mut result_ops = Vec::with_capacity(ops.len());
+                    for edit in ops {
+                        let op = match edit.op {
+                            ::cset::serde_support::SerializableSequenceOp::Insert(v) => {
+                                let typed: #elem_ty = ::cset::serde_support::serde_json::from_value(v).unwrap();
+                                ::cset::SequenceOp::Insert(::std::boxed::Box::new(typed))
+                            }
+                            ::cset::serde_support::SerializableSequenceOp::Remove => ::cset::SequenceOp::Remove,
+                            ::cset::serde_support::SerializableSequenceOp::Set(v) => {
+                                let typed: #elem_ty = ::cset::serde_support::serde_json::from_value(v).unwrap();
+                                ::cset::SequenceOp::Set(::std::boxed::Box::new(typed))
+                            }
+                            #edit_arm,
+                        };
+                        result_ops.push(::cset::SequenceEdit { index: edit.index, op });
+                    }
+                    ::cset::ChangeValue::Sequence(result_ops)
+                }
+            }
+        });
+
+    quote! {
+        pub fn to_serializable(changeset: &::cset::ChangeSet) -> ::cset::serde_support::SerializableChangeSet {
+            Self::to_serializable_impl(changeset, 0)
+        }
+
+        fn to_serializable_impl(changeset: &::cset::ChangeSet, depth: usize) -> ::cset::serde_support::SerializableChangeSet {
+            assert!(changeset.for_type::<#struct_ident>());
+            let mut changes = Vec::new();
+
+            for change in &changeset.changes {
+                let field_index = Self::__cset_resolve_index(&change.field_id, depth)
+                    .unwrap_or_else(|| unreachable!("unknown field key in changeset; call ChangeSet::remap first"));
+
+                let value = match &change.value {
+                    ::cset::ChangeValue::Value(v) => match field_index {
+                        #(#to_value_fields,)*
+                        _ => unreachable!(),
+                    },
+                    ::cset::ChangeValue::ChangeSet(cs) => match field_index {
+                        #(#to_changeset_fields)*
+                        _ => unreachable!(),
+                    },
+                    ::cset::ChangeValue::Sequence(ops) => match field_index {
+                        #(#to_sequence_fields,)*
+                        _ => unreachable!(),
+                    },
+                    ::cset::ChangeValue::Variant { .. } => {
+                        unreachable!("variant changes only apply to tracked enums")
+                    }
+                };
+
+                changes.push(::cset::serde_support::SerializableChange {
+                    field_id: change.field_id.clone(),
+                    value,
+                });
+            }
+
+            ::cset::serde_support::SerializableChangeSet {
+                target_type: ::std::any::type_name::<#struct_ident>().to_string(),
+                changes,
+            }
+        }
+
+        pub fn from_serializable(
+            serializable: ::cset::serde_support::SerializableChangeSet,
+        ) -> ::std::result::Result<::cset::ChangeSet, ::cset::serde_support::UnknownField> {
+            Self::from_serializable_impl(serializable, 0)
+        }
+
+        fn from_serializable_impl(
+            serializable: ::cset::serde_support::SerializableChangeSet,
+            depth: usize,
+        ) -> ::std::result::Result<::cset::ChangeSet, ::cset::serde_support::UnknownField> {
+            let mut changes = Vec::new();
+
+            for change in serializable.changes {
+                let field_index = match Self::__cset_resolve_index(&change.field_id, depth) {
+                    ::std::option::Option::Some(field_index) => field_index,
+                    ::std::option::Option::None => {
+                        return ::std::result::Result::Err(::cset::serde_support::UnknownField { field_id: change.field_id })
+                    }
+                };
+
+                let value = match change.value {
+                    ::cset::serde_support::SerializableValue::Value(v) => match field_index {
+                        #(#from_value_fields,)*
+                        _ => return ::std::result::Result::Err(::cset::serde_support::UnknownField { field_id: change.field_id }),
+                    },
+                    ::cset::serde_support::SerializableValue::ChangeSet(cs) => match field_index {
+                        #(#from_changeset_fields)*
+                        _ => return ::std::result::Result::Err(::cset::serde_support::UnknownField { field_id: change.field_id }),
+                    },
+                    ::cset::serde_support::SerializableValue::Sequence(ops) => match field_index {
+                        #(#from_sequence_fields,)*
+                        _ => return ::std::result::Result::Err(::cset::serde_support::UnknownField { field_id: change.field_id }),
+                    },
+                    ::cset::serde_support::SerializableValue::Variant { .. } => {
+                        return ::std::result::Result::Err(::cset::serde_support::UnknownField { field_id: change.field_id })
+                    }
+                };
+
+                changes.push(::cset::Change { field_id: change.field_id, value });
+            }
+
+            ::std::result::Result::Ok(::cset::ChangeSet::new::<#struct_ident>(changes))
+        }
+    }
+}
+
+struct TrackedVariant {
+    ident: Ident,
+    discriminant: usize,
+    fields: Vec<TrackedField>,
+}
+
+fn derive_tracked_enum(input: &DeriveInput, data: &DataEnum) -> TokenStream {
+    let enum_ident = &input.ident;
+    let vis = &input.vis;
+
+    for variant in &data.variants {
+        for field in &variant.fields {
+            if field.ident.is_none() {
+                return syn::Error::new_spanned(
+                    &variant.fields,
+                    "Cannot derive Undo for tuple variants",
+                )
+                .to_compile_error();
+            }
+        }
+    }
+
+    let variants = data
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(discriminant, variant)| {
+            let fields = variant
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| TrackedField {
+                    index,
+                    ident: field.ident.clone().unwrap(),
+                    ty: field.ty.clone(),
+                    flattened_ident: None,
+                    collection_elem_ty: None,
+                    collection_scalar: false,
+                })
+                .collect::<Vec<_>>();
+
+            TrackedVariant {
+                ident: variant.ident.clone(),
+                discriminant,
+                fields,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let draft_ident = create_draft_ident(enum_ident);
+    let state_ident = create_draft_state_ident(enum_ident);
+    let state_ident = &state_ident;
+
+    let discriminant_arms = variants.iter().map(|variant| {
+        let TrackedVariant {
+            ident,
+            discriminant,
+            fields,
+        } = variant;
+        let binders = fields.iter().map(|field| &field.ident);
+        quote!(#enum_ident::#ident { #(#binders: _,)* } => #discriminant)
+    });
+
+    let state_variants = variants.iter().map(|variant| {
+        let TrackedVariant { ident, fields, .. } = variant;
+        let field_states = fields.iter().map(|field| {
+            let TrackedField { ident, ty, .. } = field;
+            quote!(#ident: ::std::option::Option<#ty>)
+        });
+        quote!(#ident { #(#field_states,)* })
+    });
+
+    let edit_state_arms = variants.iter().map(|variant| {
+        let TrackedVariant { ident, fields, .. } = variant;
+        let binders = fields.iter().map(|field| &field.ident);
+        let inits = fields.iter().map(|field| {
+            let field_ident = &field.ident;
+            quote!(#field_ident: ::std::option::Option::None)
+        });
+        quote!(#enum_ident::#ident { #(#binders: _,)* } => #state_ident::#ident { #(#inits,)* })
+    });
+
+    let field_api_fns = variants.iter().flat_map(move |variant| {
+        let TrackedVariant {
+            ident: variant_ident,
+            fields,
+            ..
+        } = variant;
+
+        let variant_snake = to_snake_case(variant_ident);
+        fields.iter().map(move |field| {
+            let TrackedField { ident, ty, .. } = field;
+            // Qualified by variant: the same field name can appear in more
+            // than one variant (e.g. `enum Shape { Circle { radius }, ... }`
+            // next to a hypothetical second variant with its own `radius`),
+            // and unqualified `get_radius`/`set_radius` for both would
+            // collide in one `impl` block.
+            let getter = format_ident!("get_{variant_snake}_{ident}");
+            let setter = format_ident!("set_{variant_snake}_{ident}");
+
+            quote! {
+                pub fn #getter(&self) -> &#ty {
+                    if let #state_ident::#variant_ident { #ident: ::std::option::Option::Some(#ident), .. } = &self.state {
+                        return #ident;
+                    }
+
+                    match &*self.original {
+                        #enum_ident::#variant_ident { #ident, .. } => #ident,
+                        _ => panic!("field accessed on an inactive variant"),
+                    }
+                }
+
+                pub fn #setter(&mut self, #ident: #ty) {
+                    match &mut self.state {
+                        #state_ident::#variant_ident { #ident: slot, .. } => *slot = ::std::option::Option::Some(#ident),
+                        _ => panic!("field set on an inactive variant"),
+                    }
+                }
+            }
+        })
+    });
+
+    let set_variant_fns = variants.iter().map(|variant| {
+        let TrackedVariant {
+            ident: variant_ident,
+            fields,
+            ..
+        } = variant;
+        let setter = format_ident!("set_variant_{}", to_snake_case(variant_ident));
+        let params = fields.iter().map(|field| {
+            let TrackedField { ident, ty, .. } = field;
+            quote!(#ident: #ty)
+        });
+        let binders = fields.iter().map(|field| &field.ident);
+
+        quote! {
+            pub fn #setter(&mut self, #(#params,)*) {
+                self.state = #state_ident::Switch(#enum_ident::#variant_ident { #(#binders,)* });
+            }
+        }
+    });
+
+    let in_place_apply_arms = variants.iter().map(|variant| {
+        let TrackedVariant {
+            ident: variant_ident,
+            discriminant,
+            fields,
+        } = variant;
+        let variant_key = variant_ident.to_string();
+
+        let field_commits = fields.iter().map(|field| {
+            let TrackedField { index, ident, .. } = field;
+            let key = ident.to_string();
+            quote! {
+                if let ::std::option::Option::Some(#ident) = #ident {
+                    match original {
+                        #enum_ident::#variant_ident { #ident: slot, .. } => {
+                            let old_value = ::std::mem::replace(slot, #ident);
+                            changes.push(::cset::Change {
+                                // Segment 0 names the active variant, segment 1
+                                // the field within it, so a standalone field
+                                // edit carries enough information for `remap`
+                                // to resolve its key without an instance to
+                                // disambiguate against (see `remap_impl` below).
+                                field_id: field_idx
+                                    .push_field(#discriminant, ::std::option::Option::Some(#variant_key))
+                                    .push_field(#index, ::std::option::Option::Some(#key)),
+                                value: ::cset::ChangeValue::Value(::std::boxed::Box::new(old_value)),
+                            });
+                        }
+                        _ => unreachable!("draft state and original variant diverged"),
+                    }
+                }
+            }
+        });
+
+        let binders = fields.iter().map(|field| &field.ident);
+
+        quote! {
+            #state_ident::#variant_ident { #(#binders,)* } => {
+                let mut changes = Vec::new();
+                #(#field_commits)*
+                ::cset::ChangeSet::new::<#enum_ident>(changes)
+            }
+        }
+    });
+
+    let value_apply_arms = variants.iter().map(|variant| {
+        let TrackedVariant {
+            ident: variant_ident,
+            fields,
+            ..
+        } = variant;
+
+        let key_to_index_arms = fields.iter().map(|field| {
+            let TrackedField { index, ident, .. } = field;
+            let key = ident.to_string();
+            quote!(#key => ::std::option::Option::Some(#index))
+        });
+
+        let field_arms = fields.iter().map(|field| {
+            let TrackedField { index, ident, ty, .. } = field;
+            quote! {
+                #index => {
+                    let new_value = *value.downcast::<#ty>().unwrap();
+                    match self {
+                        #enum_ident::#variant_ident { #ident: slot, .. } => {
+                            let old_value = ::std::mem::replace(slot, new_value);
+                            reverse_changes.push(::cset::Change {
+                                field_id: change.field_id,
+                                value: ::cset::ChangeValue::Value(::std::boxed::Box::new(old_value)),
+                            });
+                        }
+                        _ => unreachable!("draft field index does not match the active variant"),
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #enum_ident::#variant_ident { .. } => {
+                // Resolved against this variant's own fields only: apply
+                // always runs while `self` is already the variant the
+                // change was recorded against, so (unlike `remap_impl`,
+                // which has no instance to tell it that) there's no
+                // cross-variant ambiguity to resolve here.
+                let field_index = match change.field_id.field_key(depth + 1) {
+                    ::std::option::Option::Some(key) => match key {
+                        #(#key_to_index_arms,)*
+                        _ => ::std::option::Option::None,
+                    },
+                    ::std::option::Option::None => ::std::option::Option::Some(change.field_id.field_index(depth + 1)),
+                }
+                .unwrap_or_else(|| unreachable!("unknown field key in changeset; call ChangeSet::remap first"));
+
+                match field_index {
+                    #(#field_arms,)*
+                    _ => unreachable!(),
+                }
+            }
+        }
+    });
+
+    let variant_key_to_discriminant_arms = variants.iter().map(|variant| {
+        let TrackedVariant { ident, discriminant, .. } = variant;
+        let key = ident.to_string();
+        quote!(#key => ::std::option::Option::Some(#discriminant))
+    });
+
+    let field_key_to_index_by_variant_arms = variants.iter().map(|variant| {
+        let TrackedVariant { discriminant, fields, .. } = variant;
+        let key_arms = fields.iter().map(|field| {
+            let TrackedField { index, ident, .. } = field;
+            let key = ident.to_string();
+            quote!(#key => ::std::option::Option::Some(#index))
+        });
+        quote! {
+            #discriminant => match key {
+                #(#key_arms,)*
+                _ => ::std::option::Option::None,
+            }
+        }
+    });
+
+    quote! {
+        impl #enum_ident {
+            pub fn edit(&mut self) -> #draft_ident<'_> {
+                let state = match self {
+                    #(#edit_state_arms,)*
+                };
+                #draft_ident {
+                    original: self,
+                    state,
+                }
+            }
+
+            fn __cset_discriminant(&self) -> usize {
+                match self {
+                    #(#discriminant_arms,)*
+                }
+            }
+
+            pub fn apply(&mut self, changeset: ::cset::ChangeSet) -> ::cset::ChangeSet {
+                self.apply_impl(changeset, 0)
+            }
+
+            fn apply_impl(&mut self, changeset: ::cset::ChangeSet, depth: usize) -> ::cset::ChangeSet {
+                assert!(changeset.for_type::<#enum_ident>());
+                let mut reverse_changes = Vec::new();
+
+                for change in changeset.changes {
+                    match change.value {
+                        ::cset::ChangeValue::Variant { inner, .. } => {
+                            let mut inner_changes = inner.changes;
+                            let restored = match inner_changes.pop().unwrap().value {
+                                ::cset::ChangeValue::Value(value) => {
+                                    *value.downcast::<#enum_ident>().unwrap()
+                                }
+                                _ => unreachable!("variant snapshot must be a boxed value"),
+                            };
+                            let previous = ::std::mem::replace(self, restored);
+                            let previous_discriminant = previous.__cset_discriminant();
+                            let restored_discriminant = self.__cset_discriminant();
+                            reverse_changes.push(::cset::Change {
+                                field_id: change.field_id,
+                                value: ::cset::ChangeValue::Variant {
+                                    from_discriminant: restored_discriminant,
+                                    to_discriminant: previous_discriminant,
+                                    inner: ::cset::ChangeSet::new::<#enum_ident>(vec![::cset::Change {
+                                        field_id: ::cset::FieldId::default(),
+                                        value: ::cset::ChangeValue::Value(::std::boxed::Box::new(previous)),
+                                    }]),
+                                },
+                            });
+                        }
+                        ::cset::ChangeValue::Value(value) => {
+                            match self {
+                                #(#value_apply_arms,)*
+                            }
+                        }
+                        ::cset::ChangeValue::ChangeSet(_) => unreachable!("nested changesets are not yet supported for enum fields"),
+                        ::cset::ChangeValue::Sequence(_) => unreachable!("collection fields are not yet supported on tracked enums"),
+                    }
+                }
+
+                ::cset::ChangeSet::new::<#enum_ident>(reverse_changes)
+            }
+
+            /// Resolves the variant segment (depth `depth`) of a standalone
+            /// field-edit [::cset::FieldId] by `key` first, falling back to
+            /// its stored `index`. Used only by [Self::remap_impl]: unlike
+            /// `apply_impl`, which always runs while `self` is already the
+            /// variant a change was recorded against and so can resolve a
+            /// field key against that variant's fields alone, `remap` has no
+            /// instance to disambiguate with, so the variant itself must be
+            /// resolved first from the [::cset::FieldId].
+            fn __cset_resolve_variant(field_id: &::cset::FieldId, depth: usize) -> ::std::option::Option<usize> {
+                match field_id.field_key(depth) {
+                    ::std::option::Option::Some(key) => Self::__cset_variant_key_to_discriminant(key),
+                    ::std::option::Option::None => ::std::option::Option::Some(field_id.field_index(depth)),
+                }
+            }
+
+            fn __cset_variant_key_to_discriminant(key: &str) -> ::std::option::Option<usize> {
+                match key {
+                    #(#variant_key_to_discriminant_arms,)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            /// Resolves the field segment (depth `depth`) of a standalone
+            /// field-edit [::cset::FieldId], scoped to the variant named by
+            /// `discriminant` (as already resolved by
+            /// [Self::__cset_resolve_variant]) so a field name shared by two
+            /// variants at different indices can't be confused.
+            fn __cset_resolve_field_index_for_variant(discriminant: usize, field_id: &::cset::FieldId, depth: usize) -> ::std::option::Option<usize> {
+                match field_id.field_key(depth) {
+                    ::std::option::Option::Some(key) => Self::__cset_field_key_to_index_for_variant(discriminant, key),
+                    ::std::option::Option::None => ::std::option::Option::Some(field_id.field_index(depth)),
+                }
+            }
+
+            fn __cset_field_key_to_index_for_variant(discriminant: usize, key: &str) -> ::std::option::Option<usize> {
+                match discriminant {
+                    #(#field_key_to_index_by_variant_arms,)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn remap_impl(changeset: ::cset::ChangeSet, depth: usize) -> ::std::result::Result<::cset::ChangeSet, ::cset::UnknownField> {
+                assert!(changeset.for_type::<#enum_ident>());
+                let mut changes = Vec::new();
+                let mut unknown_keys = Vec::new();
+
+                for change in changeset.changes {
+                    match change.value {
+                        ::cset::ChangeValue::Value(value) => {
+                            if change.field_id.is_empty() {
+                                changes.push(::cset::Change { field_id: change.field_id, value: ::cset::ChangeValue::Value(value) });
+                                continue;
+                            }
+
+                            let discriminant = match Self::__cset_resolve_variant(&change.field_id, depth) {
+                                ::std::option::Option::Some(discriminant) => discriminant,
+                                ::std::option::Option::None => {
+                                    unknown_keys.push(change.field_id.field_key(depth).unwrap().to_string());
+                                    continue;
+                                }
+                            };
+
+                            let resolved_index = match Self::__cset_resolve_field_index_for_variant(discriminant, &change.field_id, depth + 1) {
+                                ::std::option::Option::Some(index) => index,
+                                ::std::option::Option::None => {
+                                    unknown_keys.push(change.field_id.field_key(depth + 1).unwrap().to_string());
+                                    continue;
+                                }
+                            };
+
+                            let mut field_id = change.field_id;
+                            field_id.set_index(depth, discriminant);
+                            field_id.set_index(depth + 1, resolved_index);
+                            changes.push(::cset::Change { field_id, value: ::cset::ChangeValue::Value(value) });
+                        }
+                        ::cset::ChangeValue::Variant { from_discriminant, to_discriminant, inner } => {
+                            changes.push(::cset::Change {
+                                field_id: change.field_id,
+                                value: ::cset::ChangeValue::Variant {
+                                    from_discriminant,
+                                    to_discriminant,
+                                    inner: Self::remap_impl(inner, 0)?,
+                                },
+                            });
+                        }
+                        ::cset::ChangeValue::ChangeSet(_) => unreachable!("nested changesets are not yet supported for enum fields"),
+                        ::cset::ChangeValue::Sequence(_) => unreachable!("collection fields are not yet supported on tracked enums"),
+                    }
+                }
+
+                if !unknown_keys.is_empty() {
+                    return ::std::result::Result::Err(::cset::UnknownField { keys: unknown_keys });
+                }
+
+                ::std::result::Result::Ok(::cset::ChangeSet::new::<#enum_ident>(changes))
+            }
+        }
+
+        impl ::cset::Trackable for #enum_ident {
+            fn apply(&mut self, changeset: ::cset::ChangeSet) -> ::cset::ChangeSet {
+                self.apply_impl(changeset, 0)
+            }
+
+            fn remap(changeset: ::cset::ChangeSet) -> ::std::result::Result<::cset::ChangeSet, ::cset::UnknownField> {
+                Self::remap_impl(changeset, 0)
+            }
+        }
+
+        #vis enum #state_ident {
+            #(#state_variants,)*
+            Switch(#enum_ident),
+        }
+
+        #vis struct #draft_ident<'b> {
+            original: &'b mut #enum_ident,
+            state: #state_ident,
+        }
+
+        impl<'b> #draft_ident<'b> {
+            #(#field_api_fns)*
+            #(#set_variant_fns)*
+
+            pub fn apply(self) -> ::cset::ChangeSet {
+                self.apply_impl(::cset::FieldId::default())
+            }
+
+            fn apply_impl(self, field_idx: ::cset::FieldId) -> ::cset::ChangeSet {
+                let #draft_ident { original, state } = self;
+
+                match state {
+                    #state_ident::Switch(new_value) => {
+                        let old_value = ::std::mem::replace(original, new_value);
+                        let from_discriminant = original.__cset_discriminant();
+                        let to_discriminant = old_value.__cset_discriminant();
+                        ::cset::ChangeSet::new::<#enum_ident>(vec![::cset::Change {
+                            field_id: field_idx,
+                            value: ::cset::ChangeValue::Variant {
+                                from_discriminant,
+                                to_discriminant,
+                                inner: ::cset::ChangeSet::new::<#enum_ident>(vec![::cset::Change {
+                                    field_id: ::cset::FieldId::default(),
+                                    value: ::cset::ChangeValue::Value(::std::boxed::Box::new(old_value)),
+                                }]),
+                            },
+                        }])
+                    }
+                    #(#in_place_apply_arms,)*
+                }
+            }
+        }
     }
 }
 
+fn to_snake_case(ident: &Ident) -> String {
+    let mut out = String::new();
+    for (i, ch) in ident.to_string().chars().enumerate() {
+        if ch.is_uppercase() {
+            if i != 0 {
+                out.push('_');
+            }
+            out.extend(ch.to_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+fn create_draft_state_ident(ident: &Ident) -> Ident {
+    format_ident!("{ident}DraftState")
+}
+
 fn derive_draft_struct(struct_ident: &Ident, fields: &[TrackedField]) -> TokenStream {
     let draft_ident = create_draft_ident(struct_ident);
 
     let draft_fields = fields.iter().map(|field| {
-        let TrackedField { ident, ty, flattened_ident, .. } = field;
+        let TrackedField { ident, ty, flattened_ident, collection_elem_ty, .. } = field;
 
         if let Some(flattened_ident) = flattened_ident {
             let draft_ident = create_draft_ident(flattened_ident);
             quote!(#ident: #draft_ident<'b>)
+        } else if let Some(elem_ty) = collection_elem_ty {
+            quote!(#ident: ::cset::CollectionDraft<'b, #elem_ty>)
         } else {
             quote!(#ident: ::cset::DraftField::<'b, #ty>)
         }
     });
 
     let field_api_fns = fields.iter().map(|field| {
-        let TrackedField { ident, ty, flattened_ident, .. } = field;
+        let TrackedField { ident, ty, flattened_ident, collection_elem_ty, collection_scalar, .. } = field;
         let dirty_checker = create_dirty_check_ident(ident);
         let resetter = create_resetter_ident(ident);
-                
+
         if let Some(flattened_ident) = flattened_ident {
             let editor = format_ident!("edit_{ident}");
-            let flattened_draft_ident = create_draft_ident(flattened_ident); 
+            let flattened_draft_ident = create_draft_ident(flattened_ident);
             quote! {
                 pub fn #editor(&mut self) -> &mut #flattened_draft_ident<'b> {
                     &mut self.#ident
@@ -192,6 +1257,52 @@ fn derive_draft_struct(struct_ident: &Ident, fields: &[TrackedField]) -> TokenSt
                     self.#ident.is_dirty()
                 }
 
+                pub fn #resetter(&mut self) {
+                    self.#ident.reset();
+                }
+            }
+        } else if let Some(elem_ty) = collection_elem_ty {
+            let inserter = format_ident!("insert_{ident}");
+            let remover = format_ident!("remove_{ident}");
+            let setter = format_ident!("set_{ident}");
+            let element_edit_fns = if *collection_scalar {
+                quote!()
+            } else {
+                let elem_ident = flattened_struct_ident(elem_ty);
+                let elem_draft_ident = create_draft_ident(&elem_ident);
+                let editor = format_ident!("edit_{ident}");
+                quote! {
+                    /// Queues an edit to the element at `index`: `edit` is
+                    /// only run when this draft's own `apply` runs, in the
+                    /// same position as the insert/remove/set ops around it.
+                    pub fn #editor(&mut self, index: usize, edit: impl FnOnce(&mut #elem_draft_ident<'_>) + 'b) {
+                        self.#ident.queue_edit(index, move |element: &mut #elem_ty| {
+                            let mut element_draft = element.edit();
+                            edit(&mut element_draft);
+                            element_draft.apply()
+                        });
+                    }
+                }
+            };
+            quote! {
+                pub fn #inserter(&mut self, index: usize, value: #elem_ty) {
+                    self.#ident.insert(index, value);
+                }
+
+                pub fn #remover(&mut self, index: usize) {
+                    self.#ident.remove(index);
+                }
+
+                pub fn #setter(&mut self, index: usize, value: #elem_ty) {
+                    self.#ident.set(index, value);
+                }
+
+                #element_edit_fns
+
+                pub fn #dirty_checker(&self) -> bool {
+                    self.#ident.is_dirty()
+                }
+
                 pub fn #resetter(&mut self) {
                     self.#ident.reset();
                 }
@@ -237,20 +1348,21 @@ fn derive_draft_struct(struct_ident: &Ident, fields: &[TrackedField]) -> TokenSt
 
     let field_commits = fields.iter().map(|field| {
         let TrackedField { index, ident, flattened_ident, .. } = field;
+        let key = ident.to_string();
 
         if flattened_ident.is_some() {
             quote! {
                 {
-                    let new_field_idx = field_idx.push_field(#index);
+                    let new_field_idx = field_idx.push_field(#index, ::std::option::Option::Some(#key));
                     changes.push(::cset::Change {
                         field_id: new_field_idx.clone(),
                         value: ::cset::ChangeValue::ChangeSet(self.#ident.apply_impl(new_field_idx)),
                     });
                 }
             }
-        } else {   
+        } else {
             quote! {
-                if let Some(change) = self.#ident.apply(field_idx.push_field(#index)) {
+                if let Some(change) = self.#ident.apply(field_idx.push_field(#index, ::std::option::Option::Some(#key))) {
                     changes.push(change);
                 }
             }
@@ -314,11 +1426,67 @@ fn get_meta_items(attr: &Attribute) -> syn::Result<Vec<NestedMeta>> {
     }
 }
 
-fn flattened_struct_ident(ty: &Type) -> Ident {
+/// Checks for a container-level `#[track(name)]` attribute, as opposed to
+/// the field-level ones (`flatten`, `collection`) parsed in
+/// `derive_tracked_struct`.
+fn has_container_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| {
+        get_meta_items(attr).unwrap().iter().any(|meta| match meta {
+            NestedMeta::Meta(Meta::Path(path)) => path.is_ident(name),
+            _ => false,
+        })
+    })
+}
+
+/// Returns the bare ident of `ty` if it's a simple type path (e.g. `Bar`,
+/// not `foo::Bar` or `Foo<T>`) — the only shape whose generated draft type
+/// (`BarDraft`) can be named by this derive.
+fn simple_path_ident(ty: &Type) -> Option<Ident> {
     match ty {
-        Type::Path(path) => {
-            path.path.get_ident().unwrap().clone()
-        },
-        _ => todo!(),
+        Type::Path(path) => path.path.get_ident().cloned(),
+        _ => None,
     }
+}
+
+/// Panics if `ty` isn't a simple type path. Callers must validate this with
+/// [simple_path_ident] (emitting a spanned compile error) before reaching
+/// code generation that calls this.
+fn flattened_struct_ident(ty: &Type) -> Ident {
+    simple_path_ident(ty).expect("caller must validate the type path before generating code")
+}
+
+/// Returns `Some(is_scalar)` for a field carrying `#[track(collection)]`
+/// (tracked elements, `is_scalar = false`) or `#[track(collection(scalar))]`
+/// (plain-value elements with no `Track` impl, `is_scalar = true`), or
+/// `None` if the field carries neither.
+fn collection_attr(attrs: &[Attribute]) -> Option<bool> {
+    attrs
+        .iter()
+        .flat_map(|attr| get_meta_items(attr).unwrap())
+        .find_map(|meta| match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("collection") => Some(false),
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("collection") => {
+                Some(list.nested.iter().any(|nested| {
+                    matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("scalar"))
+                }))
+            }
+            _ => None,
+        })
+}
+
+fn vec_elem_type(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    })
 }
\ No newline at end of file
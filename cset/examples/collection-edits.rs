@@ -0,0 +1,58 @@
+use cset::Track;
+
+#[derive(Track, Debug, PartialEq)]
+struct Item {
+    value: i32,
+}
+
+#[derive(Track, Debug, PartialEq)]
+struct Document {
+    #[track(collection)]
+    items: Vec<Item>,
+    #[track(collection(scalar))]
+    tags: Vec<String>,
+}
+
+fn main() {
+    let mut doc = Document {
+        items: vec![Item { value: 1 }, Item { value: 2 }],
+        tags: vec!["a".to_string(), "b".to_string()],
+    };
+
+    // A queued element edit isn't written until `apply()` runs: dropping the
+    // draft after calling `edit_items` rolls it back completely, same as
+    // every other op on this draft.
+    {
+        let mut draft = doc.edit();
+        draft.edit_items(0, |item| item.set_value(99));
+    }
+    assert_eq!(doc.items[0], Item { value: 1 });
+
+    // Insert/edit/remove on one draft all stay queued and replay in the
+    // order they were recorded.
+    let mut draft = doc.edit();
+    draft.insert_items(1, Item { value: 50 });
+    draft.edit_items(0, |item| item.set_value(10));
+    draft.remove_items(2); // the original `Item { value: 2 }`, now shifted to index 2
+    let undo = draft.apply();
+    assert_eq!(doc.items, vec![Item { value: 10 }, Item { value: 50 }]);
+
+    doc.apply(undo);
+    assert_eq!(doc.items, vec![Item { value: 1 }, Item { value: 2 }]);
+
+    // `#[track(collection(scalar))]` is for elements that don't derive
+    // `Track` (plain values like `String`): only insert/remove/set are
+    // generated, with no `edit_*` to open an element draft that doesn't
+    // exist.
+    let mut draft = doc.edit();
+    draft.set_tags(0, "z".to_string());
+    draft.insert_tags(1, "mid".to_string());
+    let undo = draft.apply();
+    assert_eq!(
+        doc.tags,
+        vec!["z".to_string(), "mid".to_string(), "b".to_string()]
+    );
+
+    doc.apply(undo);
+    assert_eq!(doc.tags, vec!["a".to_string(), "b".to_string()]);
+}
@@ -0,0 +1,132 @@
+use cset::{Change, ChangeSet, ChangeValue, FieldId, Track, Trackable};
+
+// `label` sits at index 0 in `Circle` but index 1 in `Square`, and `radius`
+// is the other way around -- the layout that would confuse a field
+// resolver that isn't scoped to a single variant.
+#[derive(Track, Debug, PartialEq)]
+enum Shape {
+    Circle { label: String, radius: f64 },
+    Square { radius: f64, label: String },
+}
+
+fn main() {
+    let mut circle = Shape::Circle {
+        label: "a".to_string(),
+        radius: 1.0,
+    };
+
+    // Variant-qualified accessors (`get_circle_label`/`set_circle_label` vs.
+    // `get_square_label`/`set_square_label`) never collide, even though both
+    // variants have a field named `label`.
+    let mut draft = circle.edit();
+    draft.set_circle_label("b".to_string());
+    let undo = draft.apply();
+    assert_eq!(
+        circle,
+        Shape::Circle {
+            label: "b".to_string(),
+            radius: 1.0
+        }
+    );
+
+    circle.apply(undo);
+    assert_eq!(
+        circle,
+        Shape::Circle {
+            label: "a".to_string(),
+            radius: 1.0
+        }
+    );
+
+    // `remap` has no live instance to disambiguate with, so it must resolve
+    // a field key using only the variant named by the change's own variant
+    // segment. Build a changeset with a deliberately wrong index (as if it
+    // had drifted since it was recorded) to prove the key -- scoped to
+    // `Circle` -- wins rather than the stale index, or `Square`'s `label`
+    // slot.
+    let stale = ChangeSet::new::<Shape>(vec![Change {
+        field_id: FieldId::default()
+            .push_field(99, Some("Circle"))
+            .push_field(99, Some("label")),
+        value: ChangeValue::Value(Box::new("c".to_string())),
+    }]);
+    let remapped = Shape::remap(stale).expect("label exists on Circle");
+
+    let mut circle2 = Shape::Circle {
+        label: "a".to_string(),
+        radius: 1.0,
+    };
+    circle2.apply(remapped);
+    assert_eq!(
+        circle2,
+        Shape::Circle {
+            label: "c".to_string(),
+            radius: 1.0
+        }
+    );
+
+    // In-place field edits work the same way on the other variant, whose
+    // fields sit at different indices.
+    let mut square = Shape::Square {
+        radius: 2.0,
+        label: "sq".to_string(),
+    };
+    let mut draft = square.edit();
+    draft.set_square_radius(5.0);
+    let undo = draft.apply();
+    assert_eq!(
+        square,
+        Shape::Square {
+            radius: 5.0,
+            label: "sq".to_string()
+        }
+    );
+
+    square.apply(undo);
+    assert_eq!(
+        square,
+        Shape::Square {
+            radius: 2.0,
+            label: "sq".to_string()
+        }
+    );
+
+    // Switching the active variant with a generated `set_variant_*` records
+    // the *entire* old variant -- not just its changed fields -- as the
+    // reverse, since there's no shared field layout to diff against once
+    // the variant itself has changed.
+    let mut shape = Shape::Circle {
+        label: "round".to_string(),
+        radius: 3.0,
+    };
+    let mut draft = shape.edit();
+    draft.set_variant_square(4.0, "squarish".to_string());
+    let undo = draft.apply();
+    assert_eq!(
+        shape,
+        Shape::Square {
+            radius: 4.0,
+            label: "squarish".to_string()
+        }
+    );
+
+    let redo = shape.apply(undo);
+    assert_eq!(
+        shape,
+        Shape::Circle {
+            label: "round".to_string(),
+            radius: 3.0
+        }
+    );
+
+    // `shape.apply(undo)` above returned its own reverse, so replaying it
+    // switches the variant forward again -- a full undo/redo round trip.
+    shape.apply(redo);
+    assert_eq!(
+        shape,
+        Shape::Square {
+            radius: 4.0,
+            label: "squarish".to_string()
+        }
+    );
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use cset::{History, Track};
+
+#[derive(Track, Debug, PartialEq)]
+struct Item {
+    value: i32,
+}
+
+#[derive(Track, Debug, PartialEq)]
+struct Document {
+    #[track(collection)]
+    items: Vec<Item>,
+}
+
+fn main() {
+    let mut doc = Document {
+        items: vec![Item { value: 1 }],
+    };
+    // A wide group window so the two pushes below coalesce into one step.
+    let mut history = History::<Document>::new(10, Duration::from_secs(60));
+
+    let mut draft = doc.edit();
+    draft.insert_items(1, Item { value: 2 });
+    history.push(draft.apply());
+
+    let mut draft = doc.edit();
+    draft.insert_items(2, Item { value: 3 });
+    history.push(draft.apply());
+
+    assert_eq!(
+        doc.items,
+        vec![Item { value: 1 }, Item { value: 2 }, Item { value: 3 }]
+    );
+
+    // Both pushes landed in the same coalesced undo step, so a single undo
+    // must revert both inserts, not just the most recent one.
+    history.undo(&mut doc);
+    assert_eq!(doc.items, vec![Item { value: 1 }]);
+
+    history.redo(&mut doc);
+    assert_eq!(
+        doc.items,
+        vec![Item { value: 1 }, Item { value: 2 }, Item { value: 3 }]
+    );
+}
@@ -0,0 +1,67 @@
+use cset::{Lens, Track};
+
+#[derive(Track, Debug, PartialEq)]
+struct Style {
+    hue: i32,
+}
+
+#[derive(Track, Debug, PartialEq)]
+struct Panel {
+    size: i32,
+    #[track(flatten)]
+    style: Style,
+}
+
+fn main() {
+    let mut panel = Panel {
+        size: 1,
+        style: Style { hue: 2 },
+    };
+    let mut draft = panel.edit();
+
+    // A bare, single-hop lens reads/writes through the draft exactly like
+    // the generated `get_size`/`set_size` accessors would.
+    draft.set_size(10);
+    assert_eq!(*draft.focus(&Panel::SIZE_LENS), 10);
+    draft.set_with(&Panel::SIZE_LENS, 99);
+    assert_eq!(*draft.focus(&Panel::SIZE_LENS), 99);
+
+    // `.then()` composes through a flattened field to reach one of its own
+    // fields, the same way `Panel::STYLE_LENS.then(Style::HUE_LENS)` would
+    // be built up from two independently-derived lenses.
+    draft.edit_style().set_hue(20);
+    let style_hue = Panel::STYLE_LENS.then(Style::HUE_LENS);
+    assert_eq!(*draft.focus(&style_hue), 20);
+    draft.set_with(&style_hue, 55);
+    assert_eq!(*draft.focus(&style_hue), 55);
+
+    let undo = draft.apply();
+    assert_eq!(
+        panel,
+        Panel {
+            size: 99,
+            style: Style { hue: 55 }
+        }
+    );
+    panel.apply(undo);
+    assert_eq!(
+        panel,
+        Panel {
+            size: 1,
+            style: Style { hue: 2 }
+        }
+    );
+
+    // `Panel::STYLE_LENS` on its own targets the flattened field itself, not
+    // a field within it. `style`'s sub-draft holds each of `Style`'s own
+    // fields as a separate exclusive borrow rather than a single `&Style`,
+    // so there's no effective value for a bare flatten-terminal lens to
+    // read -- focus/set_with panic rather than returning something
+    // meaningless.
+    let draft = panel.edit();
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        draft.focus(&Panel::STYLE_LENS);
+    }))
+    .is_err();
+    assert!(panicked);
+}
@@ -0,0 +1,42 @@
+use cset::serde_support::SerializableChangeSet;
+use cset::{FieldId, Track};
+
+#[derive(Track, Debug, PartialEq)]
+#[track(serde)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {
+    let mut point = Point { x: 1, y: 2 };
+    let mut draft = point.edit();
+    draft.set_x(10);
+    let undo = draft.apply();
+    assert_eq!(point, Point { x: 10, y: 2 });
+
+    // `to_serializable`/`from_serializable` mirror a changeset through JSON
+    // without losing enough information to apply it back.
+    let serializable = Point::to_serializable(&undo);
+    let json = cset::serde_support::serde_json::to_string(&serializable).unwrap();
+    let parsed: SerializableChangeSet = cset::serde_support::serde_json::from_str(&json).unwrap();
+    let restored = Point::from_serializable(parsed).expect("all fields still exist on Point");
+
+    point.apply(restored);
+    assert_eq!(point, Point { x: 1, y: 2 });
+
+    // A changeset naming a field key that no longer exists on the type (as
+    // if `Point` had since dropped or renamed `z`) is reported as an error
+    // instead of panicking or silently dropping the change.
+    let stale = SerializableChangeSet {
+        target_type: std::any::type_name::<Point>().to_string(),
+        changes: vec![cset::serde_support::SerializableChange {
+            field_id: FieldId::default().push_field(99, Some("z")),
+            value: cset::serde_support::SerializableValue::Value(
+                cset::serde_support::serde_json::json!(5),
+            ),
+        }],
+    };
+    let err = Point::from_serializable(stale).expect_err("z is not a field on Point");
+    assert_eq!(err.field_id.field_key(0), Some("z"));
+}
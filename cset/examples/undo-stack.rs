@@ -1,4 +1,4 @@
-use cset::{ChangeSet, Draft, Track, Trackable};
+use cset::{ChangeSet, Track};
 
 #[derive(Track, Debug, PartialEq)]
 struct Point {
@@ -24,7 +24,7 @@ impl Document {
             let point_id = history_item.point;
             let point = &mut self.points[point_id];
 
-            let redo_changeset = point.apply_changeset(history_item.changeset);
+            let redo_changeset = point.apply(history_item.changeset);
             self.redo_stack.push(HistoryItem {
                 point: point_id,
                 changeset: redo_changeset,
@@ -37,7 +37,7 @@ impl Document {
             let point_id = history_item.point;
             let point = &mut self.points[point_id];
 
-            let undo_changeset = point.apply_changeset(history_item.changeset);
+            let undo_changeset = point.apply(history_item.changeset);
             self.undo_stack.push(HistoryItem {
                 point: point_id,
                 changeset: undo_changeset,
@@ -47,7 +47,10 @@ impl Document {
 
     fn set_point_pos(&mut self, id: usize, x: i32, y: i32) {
         self.redo_stack.clear();
-        let undo_changeset = self.points[id].edit().set_x(x).set_y(y).commit();
+        let mut draft = self.points[id].edit();
+        draft.set_x(x);
+        draft.set_y(y);
+        let undo_changeset = draft.apply();
         self.undo_stack.push(HistoryItem {
             point: id,
             changeset: undo_changeset,
@@ -60,9 +60,22 @@
 //! ```
 
 use std::any::{Any, TypeId};
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
 
 pub use cset_derive::Track;
 
+/// Implemented by every `#[derive(Track)]` type, so generic code (like
+/// [History]) can apply a [ChangeSet] without naming the concrete type.
+pub trait Trackable {
+    fn apply(&mut self, changeset: ChangeSet) -> ChangeSet;
+
+    /// Rewrites a [ChangeSet]'s field paths to match this type's current
+    /// field layout, resolving each [FieldSegment]'s `key` back to an index.
+    /// Used by [ChangeSet::remap].
+    fn remap(changeset: ChangeSet) -> Result<ChangeSet, UnknownField>;
+}
+
 #[derive(Debug)]
 pub struct ChangeSet {
     pub target_type: TypeId,
@@ -80,12 +93,232 @@ impl ChangeSet {
     pub fn for_type<T: 'static>(&self) -> bool {
         self.target_type == TypeId::of::<T>()
     }
+
+    /// Merges `newer` into `older`, for every field path present in both,
+    /// combining the two so that applying the result once undoes both edits
+    /// in one step; field paths unique to `newer` are unioned in as-is.
+    ///
+    /// - [ChangeValue::Value]: `older`'s value is kept (it was captured
+    ///   first, so it holds the more original value).
+    /// - [ChangeValue::ChangeSet]: merged recursively by this same rule.
+    /// - [ChangeValue::Sequence]: `newer`'s ops are undone first (they were
+    ///   applied on top of `older`'s), so the combined op list is `newer`'s
+    ///   ops followed by `older`'s — dropping either side would leave that
+    ///   half of the edit un-reverted by a single undo.
+    /// - [ChangeValue::Variant]: a variant reverse is an absolute snapshot
+    ///   restore rather than a relative delta, so `older`'s snapshot
+    ///   (`inner`) is already the correct combined target; only
+    ///   `from_discriminant` is updated to `newer`'s, so the merged entry
+    ///   still accurately reports the most recent variant it would be
+    ///   undoing from.
+    ///
+    /// Both changesets must target the same type; used by [History] to
+    /// coalesce consecutive edits into a single undo step.
+    pub fn coalesce(older: ChangeSet, newer: ChangeSet) -> ChangeSet {
+        assert_eq!(
+            older.target_type, newer.target_type,
+            "cannot coalesce changesets for different types"
+        );
+
+        let mut changes = older.changes;
+
+        for incoming in newer.changes {
+            let existing_index = changes
+                .iter()
+                .position(|change| change.field_id.path() == incoming.field_id.path());
+
+            match existing_index {
+                Some(index) => {
+                    let existing = changes.remove(index);
+                    let value = match (existing.value, incoming.value) {
+                        (ChangeValue::ChangeSet(existing_inner), ChangeValue::ChangeSet(incoming_inner)) => {
+                            ChangeValue::ChangeSet(ChangeSet::coalesce(existing_inner, incoming_inner))
+                        }
+                        (ChangeValue::Sequence(existing_ops), ChangeValue::Sequence(incoming_ops)) => {
+                            let mut combined_ops = incoming_ops;
+                            combined_ops.extend(existing_ops);
+                            ChangeValue::Sequence(combined_ops)
+                        }
+                        (
+                            ChangeValue::Variant { to_discriminant, inner, .. },
+                            ChangeValue::Variant { from_discriminant, .. },
+                        ) => ChangeValue::Variant {
+                            from_discriminant,
+                            to_discriminant,
+                            inner,
+                        },
+                        (existing_value, _) => existing_value,
+                    };
+                    changes.insert(
+                        index,
+                        Change {
+                            field_id: existing.field_id,
+                            value,
+                        },
+                    );
+                }
+                None => changes.push(incoming),
+            }
+        }
+
+        ChangeSet {
+            target_type: older.target_type,
+            changes,
+        }
+    }
+
+    /// Rewrites `self`'s field paths to match `T`'s *current* field layout,
+    /// resolving each [FieldSegment]'s `key` back to an index via `T`.
+    ///
+    /// Positional [FieldId]s survive struct evolution only by luck; a
+    /// changeset that was built against an older field order (or with fields
+    /// inserted/removed since) silently targets the wrong field once
+    /// replayed. Remapping first lets stale history from persistence or
+    /// transport be detected and handled gracefully instead of hitting the
+    /// `unreachable!()` arms `apply_impl` falls back to for an out-of-range
+    /// index.
+    ///
+    /// Takes `self` by value rather than by reference: a [Change] may own a
+    /// `Box<dyn Any>` of a type with no `Clone` bound, so there is no way to
+    /// produce a remapped copy without consuming the original.
+    pub fn remap<T: Trackable + 'static>(self) -> Result<ChangeSet, UnknownField> {
+        assert!(self.for_type::<T>());
+        T::remap(self)
+    }
+}
+
+/// Bounded undo/redo history for a [Trackable] type.
+///
+/// [History::push] records the reverse [ChangeSet] of an edit that was just
+/// applied. Consecutive pushes that land within `group_window` of each
+/// other are coalesced into the undo stack's top entry instead of adding a
+/// new one, so e.g. several drag updates collapse into a single undo step;
+/// [History::checkpoint] forces the next push to start a fresh step
+/// regardless of timing. `max_depth` bounds the undo stack, dropping the
+/// oldest entry once exceeded.
+pub struct History<T: Trackable> {
+    undo_stack: Vec<ChangeSet>,
+    redo_stack: Vec<ChangeSet>,
+    max_depth: usize,
+    group_window: Duration,
+    last_push_at: Option<Instant>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Trackable> History<T> {
+    pub fn new(max_depth: usize, group_window: Duration) -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            max_depth,
+            group_window,
+            last_push_at: None,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn push(&mut self, reverse: ChangeSet) {
+        self.redo_stack.clear();
+
+        let now = Instant::now();
+        let within_window = self
+            .last_push_at
+            .is_some_and(|last| now.duration_since(last) <= self.group_window);
+
+        if within_window {
+            if let Some(top) = self.undo_stack.pop() {
+                self.undo_stack.push(ChangeSet::coalesce(top, reverse));
+                self.last_push_at = Some(now);
+                return;
+            }
+        }
+
+        self.undo_stack.push(reverse);
+        if self.undo_stack.len() > self.max_depth {
+            self.undo_stack.remove(0);
+        }
+        self.last_push_at = Some(now);
+    }
+
+    /// Forces the next [History::push] to start a new undo step, even if it
+    /// arrives within `group_window` of the last one.
+    pub fn checkpoint(&mut self) {
+        self.last_push_at = None;
+    }
+
+    pub fn undo(&mut self, target: &mut T) -> bool {
+        let Some(changeset) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack.push(target.apply(changeset));
+        // Without this, a push arriving within `group_window` of this undo
+        // would coalesce into the entry undo just restored to the top of
+        // the stack, rather than starting its own step.
+        self.last_push_at = None;
+        true
+    }
+
+    pub fn redo(&mut self, target: &mut T) -> bool {
+        let Some(changeset) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack.push(target.apply(changeset));
+        self.last_push_at = None;
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
 }
 
 #[derive(Debug)]
 pub enum ChangeValue {
     Value(Box<dyn Any>),
     ChangeSet(ChangeSet),
+    /// Records a switch of the active variant of a tracked enum.
+    ///
+    /// `inner` carries the full state of the variant that was switched
+    /// *away* from, boxed as a single [Change] with a default [FieldId], so
+    /// that replaying this value restores the enum to exactly the variant
+    /// and fields it had before the switch.
+    Variant {
+        from_discriminant: usize,
+        to_discriminant: usize,
+        inner: ChangeSet,
+    },
+    /// An ordered list of reversible edits to a `#[track(collection)]` field.
+    ///
+    /// Replaying the list in order (see [apply_sequence]) reproduces the
+    /// edit; the list returned from that replay is itself the reverse,
+    /// already in the right order to undo it.
+    Sequence(Vec<SequenceEdit>),
+}
+
+/// A single reversible edit within a [ChangeValue::Sequence].
+#[derive(Debug)]
+pub struct SequenceEdit {
+    pub index: usize,
+    pub op: SequenceOp,
+}
+
+#[derive(Debug)]
+pub enum SequenceOp {
+    /// Inserts the boxed value at `index`. Reversed by [SequenceOp::Remove].
+    Insert(Box<dyn Any>),
+    /// Removes the element at `index`. Reversed by [SequenceOp::Insert] of
+    /// the removed value.
+    Remove,
+    /// Replaces the element at `index` with the boxed value. Reversed by
+    /// another `Set` carrying the replaced value.
+    Set(Box<dyn Any>),
+    /// Applies a [ChangeSet] to the tracked element at `index`. Reversed by
+    /// the [ChangeSet] that applying it produces.
+    Edit(ChangeSet),
 }
 
 #[derive(Debug)]
@@ -120,17 +353,442 @@ impl<'b, T> DraftField<'b, T> {
     }
 }
 
+/// Draft for a `#[track(collection)]` field, recording insertions, removals,
+/// replacements, and (for tracked elements) per-element edits as an ordered,
+/// reversible op list.
+///
+/// Every op, including element edits, stays queued here until
+/// [CollectionDraft::apply] is called, same as every other draft in this
+/// crate: nothing is written to `original` before then, so dropping the
+/// draft rolls everything back. The `edit_*` accessor the derive emits for a
+/// tracked element calls [CollectionDraft::queue_edit] with a closure that
+/// opens the element's own draft, applies the caller's edit to it, and
+/// commits it — but only once `apply` actually runs that closure in order
+/// alongside the insert/remove/set ops around it.
+pub struct CollectionDraft<'b, T: 'static> {
+    pub original: &'b mut Vec<T>,
+    ops: Vec<PendingSequenceEdit<'b, T>>,
+}
+
+struct PendingSequenceEdit<'b, T> {
+    index: usize,
+    op: PendingSequenceOp<'b, T>,
+}
+
+enum PendingSequenceOp<'b, T> {
+    Insert(T),
+    Remove,
+    Set(T),
+    Edit(Box<dyn FnOnce(&mut T) -> ChangeSet + 'b>),
+}
+
+impl<'b, T: std::fmt::Debug> std::fmt::Debug for CollectionDraft<'b, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CollectionDraft")
+            .field("original", &self.original)
+            .field("pending_ops", &self.ops.len())
+            .finish()
+    }
+}
+
+impl<'b, T> CollectionDraft<'b, T> {
+    pub fn new(original: &'b mut Vec<T>) -> Self {
+        Self {
+            original,
+            ops: Vec::new(),
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.ops.push(PendingSequenceEdit {
+            index,
+            op: PendingSequenceOp::Insert(value),
+        });
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.ops.push(PendingSequenceEdit {
+            index,
+            op: PendingSequenceOp::Remove,
+        });
+    }
+
+    pub fn set(&mut self, index: usize, value: T) {
+        self.ops.push(PendingSequenceEdit {
+            index,
+            op: PendingSequenceOp::Set(value),
+        });
+    }
+
+    /// Queues an edit to the element at `index`: `edit` is not run until
+    /// [CollectionDraft::apply] is called, at which point it receives the
+    /// element itself and returns the reverse [ChangeSet] of whatever it did
+    /// to it (as produced by the element's own generated draft `.apply()`).
+    pub fn queue_edit(&mut self, index: usize, edit: impl FnOnce(&mut T) -> ChangeSet + 'b) {
+        self.ops.push(PendingSequenceEdit {
+            index,
+            op: PendingSequenceOp::Edit(Box::new(edit)),
+        });
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        !self.ops.is_empty()
+    }
+
+    pub fn reset(&mut self) {
+        self.ops.clear();
+    }
+
+    pub fn apply(self, field_idx: FieldId) -> Option<Change> {
+        if self.ops.is_empty() {
+            return None;
+        }
+
+        let original = self.original;
+        let mut reverse_ops = Vec::with_capacity(self.ops.len());
+
+        for edit in self.ops {
+            let reverse_op = match edit.op {
+                PendingSequenceOp::Insert(value) => {
+                    original.insert(edit.index, value);
+                    SequenceOp::Remove
+                }
+                PendingSequenceOp::Remove => {
+                    let old_value = original.remove(edit.index);
+                    SequenceOp::Insert(Box::new(old_value))
+                }
+                PendingSequenceOp::Set(value) => {
+                    let old_value = std::mem::replace(&mut original[edit.index], value);
+                    SequenceOp::Set(Box::new(old_value))
+                }
+                PendingSequenceOp::Edit(edit_fn) => {
+                    let reverse = edit_fn(&mut original[edit.index]);
+                    SequenceOp::Edit(reverse)
+                }
+            };
+            reverse_ops.push(SequenceEdit {
+                index: edit.index,
+                op: reverse_op,
+            });
+        }
+
+        reverse_ops.reverse();
+        Some(Change {
+            field_id: field_idx,
+            value: ChangeValue::Sequence(reverse_ops),
+        })
+    }
+}
+
+/// Replays a previously-recorded [SequenceEdit] list against `original`,
+/// returning the inverted, index-adjusted list that undoes it.
+///
+/// `apply_edit` applies a nested [ChangeSet] to a single element; the
+/// generated `apply_impl` for a `#[track(collection)]` field passes in the
+/// element's own (type-specific) `apply` method.
+pub fn apply_sequence<T: 'static>(
+    original: &mut Vec<T>,
+    ops: Vec<SequenceEdit>,
+    mut apply_edit: impl FnMut(&mut T, ChangeSet) -> ChangeSet,
+) -> Vec<SequenceEdit> {
+    let mut reverse_ops = Vec::with_capacity(ops.len());
+
+    for edit in ops {
+        let reverse_op = match edit.op {
+            SequenceOp::Insert(value) => {
+                original.insert(edit.index, *value.downcast::<T>().unwrap());
+                SequenceOp::Remove
+            }
+            SequenceOp::Remove => {
+                let old_value = original.remove(edit.index);
+                SequenceOp::Insert(Box::new(old_value))
+            }
+            SequenceOp::Set(value) => {
+                let new_value = *value.downcast::<T>().unwrap();
+                let old_value = std::mem::replace(&mut original[edit.index], new_value);
+                SequenceOp::Set(Box::new(old_value))
+            }
+            SequenceOp::Edit(changeset) => {
+                let reverse = apply_edit(&mut original[edit.index], changeset);
+                SequenceOp::Edit(reverse)
+            }
+        };
+        reverse_ops.push(SequenceEdit {
+            index: edit.index,
+            op: reverse_op,
+        });
+    }
+
+    reverse_ops.reverse();
+    reverse_ops
+}
+
+/// One hop of a [FieldId]'s path: the field's position in declaration order
+/// (used unless it's been superseded) alongside a compile-time interned name
+/// for the same field, emitted by the derive so that [ChangeSet::remap] can
+/// recover the correct index after the struct's fields have been reordered
+/// or grown new ones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldSegment {
+    pub index: usize,
+    pub key: Option<&'static str>,
+}
+
+// `&'static str` can't derive `Deserialize` (there's no way to borrow from
+// the input for that long), so this leaks the deserialized key instead.
+// Changesets are deserialized rarely and a [FieldId] lives for the life of
+// the changeset it's part of, so the leak is in keeping with the crate's
+// preference for simple code over micro-optimizing a cold path.
+#[cfg(feature = "serde")]
+impl serde::Serialize for FieldSegment {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        struct Repr<'a> {
+            index: usize,
+            key: Option<&'a str>,
+        }
+        Repr {
+            index: self.index,
+            key: self.key,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FieldSegment {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Repr {
+            index: usize,
+            key: Option<String>,
+        }
+        let repr = Repr::deserialize(deserializer)?;
+        Ok(FieldSegment {
+            index: repr.index,
+            key: repr.key.map(|key| &*Box::leak(key.into_boxed_str())),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Default)]
-pub struct FieldId(Vec<usize>);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldId(Vec<FieldSegment>);
 
 impl FieldId {
-    pub fn push_field(&self, child_field: usize) -> Self {
+    pub fn push_field(&self, index: usize, key: Option<&'static str>) -> Self {
         let mut new = self.clone();
-        new.0.push(child_field);
+        new.0.push(FieldSegment { index, key });
         new
     }
 
     pub fn field_index(&self, depth: usize) -> usize {
-        self.0[depth]
+        self.0[depth].index
+    }
+
+    pub fn field_key(&self, depth: usize) -> Option<&'static str> {
+        self.0[depth].key
+    }
+
+    /// Overwrites the index of the segment at `depth`, keeping its key.
+    /// Used by a generated `remap_impl` once it has resolved the segment's
+    /// key back to the field's current position.
+    pub fn set_index(&mut self, depth: usize, index: usize) {
+        self.0[depth].index = index;
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The purely positional path, ignoring any keys. Used where only
+    /// structural identity matters (e.g. matching up a [Change] in
+    /// [ChangeSet::coalesce]) rather than resolving against a current type.
+    pub fn path(&self) -> Vec<usize> {
+        self.0.iter().map(|segment| segment.index).collect()
+    }
+}
+
+/// Returned by a [Trackable::remap] implementation when one or more
+/// [FieldSegment] keys in a [ChangeSet] no longer match any field on the
+/// current type, naming every such key so stale history can be reported or
+/// discarded instead of silently misapplied.
+#[derive(Debug)]
+pub struct UnknownField {
+    pub keys: Vec<String>,
+}
+
+/// A composable path from `Root` down to one of its tracked fields.
+///
+/// A lens pairs a [FieldId] (identifying the target of a [Change]) with
+/// direct `get`/`get_mut` access to the field on `Root` itself, so it can be
+/// used as a reusable accessor independent of any particular [ChangeSet] or
+/// draft. [Lens::then] composes a `Lens<Root, Mid>` with a `Lens<Mid, Field>`
+/// into a `Lens<Root, Field>` by walking through both in turn.
+pub trait Lens<Root, Field> {
+    fn field_id(&self) -> FieldId;
+
+    fn get<'a>(&self, root: &'a Root) -> &'a Field;
+
+    fn get_mut<'a>(&self, root: &'a mut Root) -> &'a mut Field;
+
+    fn set(&self, root: &mut Root, value: Field) {
+        *self.get_mut(root) = value;
+    }
+
+    fn then<Next, Inner>(self, inner: Inner) -> ComposedLens<Self, Inner, Field>
+    where
+        Self: Sized,
+        Field: 'static,
+        Inner: Lens<Field, Next>,
+    {
+        ComposedLens {
+            outer: self,
+            inner,
+            _mid: std::marker::PhantomData,
+        }
+    }
+}
+
+/// A single-hop [Lens] onto one of `Root`'s own fields, as emitted by the
+/// derive for every non-collection field (e.g. `Foo::X_LENS`).
+///
+/// The field path is a `&'static [usize]` rather than an owned [FieldId] so
+/// that this type stays `const`-constructible.
+pub struct FieldLens<Root, Field> {
+    path: &'static [usize],
+    get: fn(&Root) -> &Field,
+    get_mut: fn(&mut Root) -> &mut Field,
+}
+
+impl<Root, Field> FieldLens<Root, Field> {
+    pub const fn new(
+        path: &'static [usize],
+        get: fn(&Root) -> &Field,
+        get_mut: fn(&mut Root) -> &mut Field,
+    ) -> Self {
+        Self {
+            path,
+            get,
+            get_mut,
+        }
+    }
+}
+
+impl<Root, Field> Lens<Root, Field> for FieldLens<Root, Field> {
+    fn field_id(&self) -> FieldId {
+        FieldId(
+            self.path
+                .iter()
+                .map(|&index| FieldSegment { index, key: None })
+                .collect(),
+        )
+    }
+
+    fn get<'a>(&self, root: &'a Root) -> &'a Field {
+        (self.get)(root)
+    }
+
+    fn get_mut<'a>(&self, root: &'a mut Root) -> &'a mut Field {
+        (self.get_mut)(root)
+    }
+}
+
+/// The result of [Lens::then]: a `Lens<Root, Field>` built by running
+/// `outer` (`Root` -> `Mid`) followed by `inner` (`Mid` -> `Field`).
+pub struct ComposedLens<Outer, Inner, Mid> {
+    outer: Outer,
+    inner: Inner,
+    _mid: std::marker::PhantomData<fn() -> Mid>,
+}
+
+impl<Root, Mid: 'static, Field, Outer, Inner> Lens<Root, Field> for ComposedLens<Outer, Inner, Mid>
+where
+    Outer: Lens<Root, Mid>,
+    Inner: Lens<Mid, Field>,
+{
+    fn field_id(&self) -> FieldId {
+        let mut segments = self.outer.field_id().0;
+        segments.extend(self.inner.field_id().0);
+        FieldId(segments)
+    }
+
+    fn get<'a>(&self, root: &'a Root) -> &'a Field {
+        self.inner.get(self.outer.get(root))
+    }
+
+    fn get_mut<'a>(&self, root: &'a mut Root) -> &'a mut Field {
+        self.inner.get_mut(self.outer.get_mut(root))
+    }
+}
+
+/// Serializable mirror of [ChangeSet] and friends, produced by a type's
+/// `to_serializable`/`from_serializable` methods when it derives `Track`
+/// with `#[track(serde)]`.
+///
+/// `Box<dyn Any>` can't be serialized without knowing the concrete type it
+/// holds, so the derive generates a per-field dispatch table (keyed by the
+/// same numeric [FieldId] path `apply_impl` already uses) that downcasts
+/// each boxed value before handing it to `serde_json`, and re-boxes it on
+/// the way back in.
+#[cfg(feature = "serde")]
+pub mod serde_support {
+    use crate::FieldId;
+
+    // Re-exported so the derive's generated `to_serializable`/
+    // `from_serializable` methods -- which expand into the *consumer's*
+    // crate, not this one -- can call `::cset::serde_support::serde_json::*`
+    // without every `#[track(serde)]` user adding their own direct
+    // `serde_json` dependency.
+    pub use serde_json;
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct SerializableChangeSet {
+        pub target_type: String,
+        pub changes: Vec<SerializableChange>,
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct SerializableChange {
+        pub field_id: FieldId,
+        pub value: SerializableValue,
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub enum SerializableValue {
+        Value(serde_json::Value),
+        ChangeSet(SerializableChangeSet),
+        Variant {
+            from_discriminant: usize,
+            to_discriminant: usize,
+            inner: SerializableChangeSet,
+        },
+        Sequence(Vec<SerializableSequenceEdit>),
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub struct SerializableSequenceEdit {
+        pub index: usize,
+        pub op: SerializableSequenceOp,
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    pub enum SerializableSequenceOp {
+        Insert(serde_json::Value),
+        Remove,
+        Set(serde_json::Value),
+        Edit(SerializableChangeSet),
+    }
+
+    /// Returned by `from_serializable` when a [SerializableChangeSet]
+    /// targets a field path that no longer exists on the current shape of
+    /// its type.
+    #[derive(Debug)]
+    pub struct UnknownField {
+        pub field_id: FieldId,
     }
 }